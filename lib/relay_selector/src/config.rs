@@ -14,6 +14,8 @@ pub trait ConfigProvider {
     ///
     /// * `key` - The configuration key to retrieve. Expects the following keys to be supported:
     ///   - "serverAllowList": Returns Vec<String>
+    ///   - "serverBlockList": Returns Vec<String>
+    ///   - "blockList": Returns Vec<String>
     ///   - "trustLevels": Returns HashMap<String, f64>
     ///   - "vendorScores": Returns HashMap<String, f64>
     ///
@@ -116,6 +118,57 @@ pub async fn get_server_side_relay_allow_list(
     Ok(allowlist)
 }
 
+/// Fetches the server-side relay blocklist from configuration.
+///
+/// Relays on this list are hard-blocked from selection in every mode, client- or server-side,
+/// regardless of their accumulated weight.
+///
+/// # Arguments
+///
+/// * `provider` - The configuration provider to use. Is dynamically dispatched to allow the
+/// provider to be specified by JS code at runtime.
+///
+/// # Returns
+///
+/// A Vec of the URLs of blocked relays.
+pub async fn get_server_side_relay_block_list(
+    provider: &dyn ConfigProvider,
+) -> Result<Vec<String>, String> {
+    let promise = provider.get_config_value("serverBlockList");
+    let js_value = wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .map_err(|e| format!("Failed to fetch server blocklist: {:?}", e))?;
+
+    let blocklist = serde_wasm_bindgen::from_value(js_value).map_err(|e| {
+        format!("Failed to deserialize server blocklist: {:?}", e)
+    })?;
+    Ok(blocklist)
+}
+
+/// Fetches the client-side relay blocklist from configuration.
+///
+/// This is the client-side counterpart to `serverBlockList`, and is enforced regardless of
+/// whether selection is happening client- or server-side.
+///
+/// # Arguments
+///
+/// * `provider` - The configuration provider to use. Is dynamically dispatched to allow the
+/// provider to be specified by JS code at runtime.
+///
+/// # Returns
+///
+/// A Vec of the URLs of blocked relays.
+pub async fn get_relay_block_list(provider: &dyn ConfigProvider) -> Result<Vec<String>, String> {
+    let promise = provider.get_config_value("blockList");
+    let js_value = wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .map_err(|e| format!("Failed to fetch blocklist: {:?}", e))?;
+
+    let blocklist = serde_wasm_bindgen::from_value(js_value)
+        .map_err(|e| format!("Failed to deserialize blocklist: {:?}", e))?;
+    Ok(blocklist)
+}
+
 /// Gets the trust level for a specific relay URL.
 ///
 /// # Arguments
@@ -0,0 +1,34 @@
+/// How a dirty relay's persisted record should be handled when flushed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheUpdatePolicy {
+    /// Persist only the relays marked dirty since the last flush, merging them into the backend
+    /// (matches `RelayRepository::persist`'s upsert semantics). The default, and the cheapest
+    /// option for routine checkpoints.
+    WriteThrough,
+    /// Persist every relay the selector currently knows about, not just the dirty ones,
+    /// overwriting each one's persisted record. Records for relays the selector no longer knows
+    /// about are left in the backend. Useful to force a full resync, e.g. after changing backends.
+    Overwrite,
+    /// Remove dirty relays from the backend instead of writing them, via
+    /// [`super::RelayRepository::remove`]. Useful when a relay is being retired from the
+    /// selector's in-memory state and should not reappear on the next load.
+    Remove,
+}
+
+impl Default for CacheUpdatePolicy {
+    fn default() -> Self {
+        Self::WriteThrough
+    }
+}
+
+impl CacheUpdatePolicy {
+    /// Parses a `CacheUpdatePolicy` from a string, as received from a JS caller.
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "write_through" => Some(Self::WriteThrough),
+            "overwrite" => Some(Self::Overwrite),
+            "remove" => Some(Self::Remove),
+            _ => None,
+        }
+    }
+}
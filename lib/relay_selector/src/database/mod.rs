@@ -0,0 +1,11 @@
+mod cache;
+mod operations;
+mod repository;
+mod schema;
+
+pub use cache::CacheUpdatePolicy;
+pub use operations::{get_all_relays, insert_or_update, remove_relays};
+#[cfg(not(target_arch = "wasm32"))]
+pub use repository::FileRepository;
+pub use repository::{IndexedDbRepository, InMemoryRepository, RelayRepository};
+pub use schema::Relay;
@@ -34,6 +34,18 @@ pub async fn insert_or_update(store_name: &str, relays: &[schema::Relay]) -> Res
     commit_transaction(tx).await
 }
 
+pub async fn remove_relays(store_name: &str, urls: &[String]) -> Result<(), String> {
+    let db = open_database(DB_NAME).await?;
+    let tx = open_transaction(&db, store_name, TransactionMode::Readwrite).await?;
+    let store = get_object_store(&tx, store_name).await?;
+
+    for url in urls {
+        store.delete(url);
+    }
+
+    commit_transaction(tx).await
+}
+
 async fn open_database(database_name: &str) -> Result<Database, String> {
     Database::open(database_name)
         .await
@@ -0,0 +1,189 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::database::operations;
+use crate::database::schema;
+
+/// A future returned by a [`RelayRepository`] method.
+///
+/// Boxed and pinned since `RelayRepository` is used as a trait object (`dyn RelayRepository`),
+/// and not `Send` since relay selector state is confined to a single thread.
+type RepositoryFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// A pluggable persistence backend for relay statistics.
+///
+/// This abstraction decouples `RelaySelector` from any one storage engine, so the same selection
+/// logic can run in a browser (backed by IndexedDB), under Node, or server-side where IndexedDB
+/// is unavailable.
+pub trait RelayRepository {
+    /// Loads every persisted relay record.
+    fn load_all(&self) -> RepositoryFuture<'_, Result<Vec<schema::Relay>, String>>;
+
+    /// Upserts the given relay records: a record whose [`schema::Relay::key`] matches an existing
+    /// one replaces it in place, and any other record is appended. Never wholesale-replaces the
+    /// existing store, since a flush commonly only touches a handful of dirty relays.
+    fn persist(&self, relays: Vec<schema::Relay>) -> RepositoryFuture<'_, Result<(), String>>;
+
+    /// Removes the records for the given [`schema::Relay::key`]s, if present. A key with no
+    /// persisted record is silently ignored.
+    fn remove(&self, keys: Vec<String>) -> RepositoryFuture<'_, Result<(), String>>;
+}
+
+/// A `RelayRepository` backed by the browser's IndexedDB store.
+///
+/// This is the default backend, and the only one that survives a page reload in a browser
+/// context. The object store must be keyed by [`schema::Relay::key`], not `url`, so that a relay
+/// with rows in more than one variant bucket persists each row independently instead of the later
+/// `put` silently overwriting the earlier one.
+pub struct IndexedDbRepository {
+    store_name: String,
+}
+
+impl IndexedDbRepository {
+    pub fn new(store_name: &str) -> Self {
+        Self {
+            store_name: store_name.to_string(),
+        }
+    }
+}
+
+impl RelayRepository for IndexedDbRepository {
+    fn load_all(&self) -> RepositoryFuture<'_, Result<Vec<schema::Relay>, String>> {
+        let store_name = self.store_name.clone();
+        Box::pin(async move { operations::get_all_relays(&store_name).await })
+    }
+
+    fn persist(&self, relays: Vec<schema::Relay>) -> RepositoryFuture<'_, Result<(), String>> {
+        let store_name = self.store_name.clone();
+        Box::pin(async move { operations::insert_or_update(&store_name, relays.as_slice()).await })
+    }
+
+    fn remove(&self, keys: Vec<String>) -> RepositoryFuture<'_, Result<(), String>> {
+        let store_name = self.store_name.clone();
+        Box::pin(async move { operations::remove_relays(&store_name, keys.as_slice()).await })
+    }
+}
+
+/// An in-memory `RelayRepository` that never touches disk or IndexedDB.
+///
+/// Useful under Node or server-side rendering where IndexedDB is absent: records are upserted
+/// into and loaded from process memory, so the selector behaves identically, but statistics do not
+/// survive a process restart.
+pub struct InMemoryRepository {
+    relays: RefCell<Vec<schema::Relay>>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        Self {
+            relays: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl RelayRepository for InMemoryRepository {
+    fn load_all(&self) -> RepositoryFuture<'_, Result<Vec<schema::Relay>, String>> {
+        let relays = self.relays.borrow().clone();
+        Box::pin(async move { Ok(relays) })
+    }
+
+    fn persist(&self, relays: Vec<schema::Relay>) -> RepositoryFuture<'_, Result<(), String>> {
+        let mut store = self.relays.borrow_mut();
+        for relay in relays {
+            match store.iter_mut().find(|existing| existing.key() == relay.key()) {
+                Some(slot) => *slot = relay,
+                None => store.push(relay),
+            }
+        }
+        Box::pin(async move { Ok(()) })
+    }
+
+    fn remove(&self, keys: Vec<String>) -> RepositoryFuture<'_, Result<(), String>> {
+        self.relays
+            .borrow_mut()
+            .retain(|relay| !keys.contains(&relay.key()));
+        Box::pin(async move { Ok(()) })
+    }
+}
+
+/// A `RelayRepository` backed by a flat JSON file on disk.
+///
+/// Intended for native contexts, e.g. a CLI tool or a server-side renderer running under
+/// Node/Deno's native host rather than a browser, where neither IndexedDB nor an in-memory-only
+/// store is appropriate: statistics should both run outside a browser *and* survive a process
+/// restart.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FileRepository {
+    path: std::path::PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileRepository {
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl RelayRepository for FileRepository {
+    fn load_all(&self) -> RepositoryFuture<'_, Result<Vec<schema::Relay>, String>> {
+        let path = self.path.clone();
+        Box::pin(async move {
+            if !path.exists() {
+                return Ok(Vec::new());
+            }
+
+            let contents = std::fs::read_to_string(&path)
+                .map_err(|err| format!("Failed to read relay store {:?}: {:?}", path, err))?;
+            serde_json::from_str(&contents)
+                .map_err(|err| format!("Failed to parse relay store {:?}: {:?}", path, err))
+        })
+    }
+
+    fn persist(&self, relays: Vec<schema::Relay>) -> RepositoryFuture<'_, Result<(), String>> {
+        let path = self.path.clone();
+        Box::pin(async move {
+            let mut existing: Vec<schema::Relay> = if path.exists() {
+                let contents = std::fs::read_to_string(&path)
+                    .map_err(|err| format!("Failed to read relay store {:?}: {:?}", path, err))?;
+                serde_json::from_str(&contents)
+                    .map_err(|err| format!("Failed to parse relay store {:?}: {:?}", path, err))?
+            } else {
+                Vec::new()
+            };
+
+            for relay in relays {
+                match existing.iter_mut().find(|r| r.key() == relay.key()) {
+                    Some(slot) => *slot = relay,
+                    None => existing.push(relay),
+                }
+            }
+
+            let contents = serde_json::to_string(&existing)
+                .map_err(|err| format!("Failed to serialize relay store: {:?}", err))?;
+            std::fs::write(&path, contents)
+                .map_err(|err| format!("Failed to write relay store {:?}: {:?}", path, err))
+        })
+    }
+
+    fn remove(&self, keys: Vec<String>) -> RepositoryFuture<'_, Result<(), String>> {
+        let path = self.path.clone();
+        Box::pin(async move {
+            if !path.exists() {
+                return Ok(());
+            }
+
+            let contents = std::fs::read_to_string(&path)
+                .map_err(|err| format!("Failed to read relay store {:?}: {:?}", path, err))?;
+            let mut relays: Vec<schema::Relay> = serde_json::from_str(&contents)
+                .map_err(|err| format!("Failed to parse relay store {:?}: {:?}", path, err))?;
+            relays.retain(|relay| !keys.contains(&relay.key()));
+
+            let contents = serde_json::to_string(&relays)
+                .map_err(|err| format!("Failed to serialize relay store: {:?}", err))?;
+            std::fs::write(&path, contents)
+                .map_err(|err| format!("Failed to write relay store {:?}: {:?}", path, err))
+        })
+    }
+}
@@ -4,20 +4,57 @@ use serde::{Deserialize, Serialize};
 
 use crate::relay;
 use crate::relay_selector;
+use crate::weights;
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Relay {
     pub url: String,
     pub variant: relay::Variant,
     requests: u32,
     successful_requests: u32,
+    #[serde(default)]
+    ewma_ms: Option<f32>,
+    #[serde(default)]
+    samples: u32,
+    /// Legacy, unbounded response-time history from before it was collapsed into `ewma_ms`.
+    /// Populated only when deserializing a record written before that migration; never written
+    /// back out, so a relay is rewritten in the new, bounded shape the next time it is persisted.
+    #[serde(default, skip_serializing)]
     response_times: Vec<Duration>,
     trust_level: f32,
     vendor_score: f32,
+    /// Manual rank boost set by an operator. Defaults to `0` (no boost) for records persisted
+    /// before manual ranking existed.
+    #[serde(default)]
+    rank: u8,
     pub weight: f32,
+    /// Read/write/advertise capability flags. Defaults to [`relay::RelayUsage::ALL`] for records
+    /// persisted before this flag set existed, preserving their previous behavior.
+    #[serde(default)]
+    pub usage: relay::RelayUsage,
+    /// Whether the relay is manually pinned to the front of its list, regardless of computed
+    /// weight. Defaults to `false` for records persisted before pinning existed.
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 impl Relay {
+    /// Computes the composite identity a repository should key a relay's persisted row on.
+    ///
+    /// `url` alone is not unique: a relay commonly belongs to more than one [`relay::Variant`]
+    /// bucket at once (`populate_defaults` puts every default relay into `general`, `inbox`, and
+    /// `outbox` simultaneously), each persisted as its own row with its own `usage` flags.
+    /// Repositories must upsert and remove by this key, not by `url` alone, or rows for the same
+    /// relay in different buckets collide and only the last write survives.
+    pub fn storage_key(url: &str, variant: relay::Variant) -> String {
+        format!("{}::{}", url, variant.to_string())
+    }
+
+    /// This record's own composite identity. See [`Self::storage_key`].
+    pub fn key(&self) -> String {
+        Self::storage_key(&self.url, self.variant)
+    }
+
     pub fn from_repositories(
         url: &str,
         variant: relay::Variant,
@@ -32,10 +69,19 @@ impl Relay {
             variant,
             requests: statistics.requests,
             successful_requests: statistics.successful_requests,
-            response_times: statistics.response_times.clone(),
+            ewma_ms: statistics.ewma_ms,
+            samples: statistics.samples,
+            response_times: Vec::new(),
             trust_level: statistics.trust_level,
             vendor_score: statistics.vendor_score,
+            rank: statistics.rank as u8,
             weight: selector.initial_weights[url],
+            usage: selector
+                .usage
+                .get(&(url.to_string(), variant))
+                .copied()
+                .unwrap_or(relay::RelayUsage::ALL),
+            pinned: selector.pinned.contains(url),
         }
     }
 
@@ -43,9 +89,30 @@ impl Relay {
         let mut statistics = relay::Statistics::new();
         statistics.requests = self.requests;
         statistics.successful_requests = self.successful_requests;
-        statistics.response_times = self.response_times.clone();
         statistics.trust_level = self.trust_level;
         statistics.vendor_score = self.vendor_score;
+        statistics.rank = self.rank as f32;
+
+        // Migrate a legacy `response_times` history into the EWMA it would have produced, so
+        // older persisted records keep contributing to the weighting instead of being discarded.
+        statistics.ewma_ms = self
+            .ewma_ms
+            .or_else(|| Self::collapse_legacy_response_times(&self.response_times));
+        statistics.samples = if self.samples > 0 {
+            self.samples
+        } else {
+            self.response_times.len() as u32
+        };
+
         statistics
     }
+
+    /// Folds a legacy `response_times` history into the EWMA it would have produced had samples
+    /// been recorded one at a time, in order. Returns `None` for an empty history.
+    fn collapse_legacy_response_times(response_times: &[Duration]) -> Option<f32> {
+        response_times.iter().fold(None, |ewma, duration| {
+            let sample_ms = duration.as_secs_f32() * 1000.0;
+            Some(weights::update_ewma(ewma, sample_ms))
+        })
+    }
 }
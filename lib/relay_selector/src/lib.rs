@@ -32,6 +32,12 @@ thread_local! {
     /// to retrieve configuration values.
     static CONFIG_PROVIDER: RefCell<Option<config::JsConfigProvider>> = RefCell::new(None);
 
+    /// Static lifetime, thread-local persistence backend.
+    ///
+    /// Defaults to [`database::IndexedDbRepository`] when [`set_repository_backend`] is never
+    /// called, which preserves existing browser behavior.
+    static REPOSITORY: RefCell<Option<Rc<dyn database::RelayRepository>>> = RefCell::new(None);
+
     /// Use this mutex to prevent race conditions during relay selector initialization.
     static INIT_MUTEX: Rc<Mutex<()>> = Rc::new(Mutex::new(()));
 }
@@ -70,8 +76,16 @@ async fn ensure_relay_selector_initialized(store_name: &str) {
         .try_with(|provider| provider.borrow().as_ref().cloned())
         .unwrap_throw()
         .expect("Config provider must be set before initializing relay selector");
+    let config_provider: Rc<dyn config::ConfigProvider> = Rc::new(config_provider);
+
+    // Fall back to the IndexedDB backend when no backend was explicitly chosen via
+    // `set_repository_backend`, preserving existing browser behavior.
+    let repository = REPOSITORY
+        .try_with(|r| r.borrow().as_ref().map(Rc::clone))
+        .unwrap_throw()
+        .unwrap_or_else(|| Rc::new(database::IndexedDbRepository::new(store_name)));
 
-    let selector = RelaySelector::init(store_name, config_provider)
+    let selector = RelaySelector::init(repository, config_provider)
         .await
         .unwrap_throw();
 
@@ -92,6 +106,62 @@ async fn ensure_relay_selector_initialized(store_name: &str) {
     // Lock released when _guard is dropped
 }
 
+/// A single relay telemetry event accepted by [`record_batch`].
+#[derive(serde::Deserialize)]
+struct BatchEntry {
+    relay_url: String,
+    response_time: Option<f32>,
+    is_success: Option<bool>,
+    relay_type: Option<String>,
+}
+
+/// Records a batch of relay telemetry events in a single operation.
+///
+/// This is the batched counterpart to [`record_response_time`] and [`record_request`]: all
+/// entries are applied under a single selector borrow, any unknown relays are inserted once each,
+/// and the result is persisted in a single transaction, instead of one per event. Prefer this
+/// over repeated individual calls when reporting many relay results at once, e.g. after a
+/// fan-out query.
+///
+/// # Arguments
+///
+/// * `entries` - A JS array of objects shaped like `{ relay_url, response_time?, is_success?,
+///   relay_type? }`.
+#[wasm_bindgen]
+pub async fn record_batch(entries: JsValue) {
+    let entries: Vec<BatchEntry> = serde_wasm_bindgen::from_value(entries).unwrap_throw();
+
+    ensure_relay_selector_initialized(STORE_NAME).await;
+
+    let selector_rc = RELAY_SELECTOR.try_with(|rc| rc.clone()).unwrap_throw();
+    let mut selector_ref = selector_rc.borrow_mut();
+    let selector = selector_ref.as_mut().unwrap_throw();
+
+    for entry in entries {
+        let variant = match entry.relay_type {
+            Some(t) => relay::Variant::from_str(&t).unwrap_throw(),
+            None => relay::Variant::General,
+        };
+
+        if !selector.contains(&entry.relay_url) && !selector.insert(&entry.relay_url, variant).await
+        {
+            // The relay is blocked; skip recording telemetry for it.
+            continue;
+        }
+
+        if let Some(response_time) = entry.response_time {
+            let response_duration = Duration::try_from_secs_f32(response_time).unwrap_throw();
+            selector.update_weights_with_response_time(&entry.relay_url, response_duration);
+        }
+
+        if let Some(is_success) = entry.is_success {
+            selector.update_weights_with_request(&entry.relay_url, is_success);
+        }
+    }
+
+    selector.flush_dirty().await.unwrap_throw();
+}
+
 #[wasm_bindgen]
 pub async fn record_response_time(
     relay_url: &str,
@@ -111,8 +181,9 @@ pub async fn record_response_time(
     let mut selector_ref = selector_rc.borrow_mut();
     let selector = selector_ref.as_mut().unwrap_throw();
 
-    if !selector.contains(relay_url) {
-        selector.insert(relay_url, variant).await;
+    if !selector.contains(relay_url) && !selector.insert(relay_url, variant).await {
+        // The relay is blocked; do not record statistics for it.
+        return;
     }
 
     selector.update_weights_with_response_time(relay_url, response_duration)
@@ -131,8 +202,9 @@ pub async fn record_request(relay_url: &str, is_success: bool, relay_type: Optio
     let mut selector_ref = selector_rc.borrow_mut();
     let selector = selector_ref.as_mut().unwrap_throw();
 
-    if !selector.contains(relay_url) {
-        selector.insert(relay_url, variant).await;
+    if !selector.contains(relay_url) && !selector.insert(relay_url, variant).await {
+        // The relay is blocked; do not record statistics for it.
+        return;
     }
 
     selector.update_weights_with_request(relay_url, is_success)
@@ -165,6 +237,67 @@ pub fn set_config_provider(config_callback: js_sys::Function) {
         .unwrap_throw();
 }
 
+/// Sets the policy used to persist dirty relays, i.e. relays touched since the last flush.
+///
+/// # Arguments
+///
+/// * `policy` - The policy to use. May be `"write_through"` (the default; persists only dirty
+///   relays), `"overwrite"` (persists every known relay), or `"remove"` (deletes dirty relays from
+///   the backend instead of writing them).
+#[wasm_bindgen]
+pub async fn set_cache_update_policy(policy: &str) {
+    let policy = database::CacheUpdatePolicy::from_str(policy).unwrap_throw();
+
+    ensure_relay_selector_initialized(STORE_NAME).await;
+
+    let selector_rc = RELAY_SELECTOR.try_with(|rc| rc.clone()).unwrap_throw();
+    selector_rc
+        .borrow_mut()
+        .as_mut()
+        .unwrap_throw()
+        .set_cache_update_policy(policy);
+}
+
+/// Persists every known relay to the configured repository immediately, regardless of dirty
+/// state, so a caller can checkpoint on their own cadence instead of relying on the best-effort
+/// flush performed when the module is torn down (which silently loses all updates if the process
+/// ends abnormally).
+///
+/// # Errors
+///
+/// Throws an error if persistence fails.
+#[wasm_bindgen]
+pub async fn flush() -> Result<(), String> {
+    ensure_relay_selector_initialized(STORE_NAME).await;
+
+    let selector_rc = RELAY_SELECTOR.try_with(|rc| rc.clone()).unwrap_throw();
+    let selector_ref = selector_rc.borrow();
+    selector_ref.as_ref().unwrap_throw().flush().await
+}
+
+/// Sets the persistence backend used by the relay selector.
+///
+/// Call this before any other relay selector operation to choose where relay statistics are
+/// loaded from and saved to. If never called, the selector defaults to the IndexedDB-backed
+/// repository, which requires a browser context.
+///
+/// # Arguments
+///
+/// * `backend` - The backend to use. May be `"indexeddb"` (the default) or `"memory"`. The
+///   `"memory"` backend keeps relay statistics in memory only, so it can run under Node or
+///   server-side rendering where IndexedDB is absent, but statistics do not survive a process
+///   restart.
+#[wasm_bindgen]
+pub fn set_repository_backend(backend: &str) {
+    let repository: Rc<dyn database::RelayRepository> = match backend {
+        "memory" => Rc::new(database::InMemoryRepository::new()),
+        _ => Rc::new(database::IndexedDbRepository::new(STORE_NAME)),
+    };
+    REPOSITORY
+        .try_with(|r| r.borrow_mut().replace(repository))
+        .unwrap_throw();
+}
+
 /// Get a recommended relay URL based on current weights.
 ///
 /// **Important**: You must call `set_config_provider` before calling this function.
@@ -234,6 +367,80 @@ pub async fn get_relay(
     Ok(relay::RelayHandle::new(url, variant, &selector_rc))
 }
 
+/// Builds a [`relay::RelayUsage`] from optional per-flag overrides, falling back to `default` for
+/// any flag left unset. Used to translate the optional `read`/`write`/`advertise` parameters JS
+/// callers pass into wasm bindings that need a `RelayUsage`.
+fn usage_from_optional_flags(
+    read: Option<bool>,
+    write: Option<bool>,
+    advertise: Option<bool>,
+    default: relay::RelayUsage,
+) -> relay::RelayUsage {
+    relay::RelayUsage {
+        read: read.unwrap_or(default.read),
+        write: write.unwrap_or(default.write),
+        advertise: advertise.unwrap_or(default.advertise),
+    }
+}
+
+/// Get a recommended relay URL, restricted to relays whose capability flags satisfy the given
+/// requirement (e.g. "give me the top write relay").
+///
+/// Otherwise identical to [`get_relay`], which is a thin wrapper over
+/// `RelaySelector::get_relay_by_capability` passing no capability requirement.
+///
+/// **Important**: You must call `set_config_provider` before calling this function.
+///
+/// # Arguments
+///
+/// * `relay_type` - The type of relay. May be `"general"`, `"inbox"`, or `"outbox"`.
+/// * `read` - If `true`, only relays flagged readable are eligible. Unset means "don't care".
+/// * `write` - If `true`, only relays flagged writable are eligible. Unset means "don't care".
+/// * `advertise` - If `true`, only relays flagged advertise are eligible. Unset means "don't care".
+/// * `relay_rank` - The relay rank based on current weights. Defaults to `0` to select the
+/// highest-ranked relay.
+/// * `is_server_side` - Whether this function is being invoked on a server environment, rather
+/// than client-side on an end user device or in a browser. When true, only relays in the
+/// server allowlist will be selected.
+///
+/// # Returns
+///
+/// A relay handle containing the relay URL, its variant, and a private pointer to the selector.
+/// When this handle is dropped, it will notify the selector to indicate the relay is no longer in
+/// use, and the selector will update the weights accordingly.
+///
+/// # Errors
+///
+/// Throws an error if the relay type is invalid, if no relay satisfying the required capabilities
+/// is found at the requested rank, if the selected relay is not in the server allowlist (when
+/// `is_server_side` is true), or if an error occurs while selecting the relay.
+#[wasm_bindgen]
+pub async fn get_relay_by_capability(
+    relay_type: &str,
+    read: Option<bool>,
+    write: Option<bool>,
+    advertise: Option<bool>,
+    relay_rank: Option<u8>,
+    is_server_side: Option<bool>,
+) -> Result<relay::RelayHandle, String> {
+    let variant = relay::Variant::from_str(relay_type).unwrap_throw();
+    let required = usage_from_optional_flags(read, write, advertise, relay::RelayUsage::NONE);
+    let rank = relay_rank.unwrap_or(0) as usize;
+
+    ensure_relay_selector_initialized(STORE_NAME).await;
+
+    let selector_rc = RELAY_SELECTOR.try_with(|rc| rc.clone()).unwrap_throw();
+    let url = selector_rc
+        .borrow_mut()
+        .as_mut()
+        .unwrap_throw()
+        .get_relay_by_capability(variant, required, rank, is_server_side.unwrap_or(false))
+        .await
+        .unwrap_throw();
+
+    Ok(relay::RelayHandle::new(url, variant, &selector_rc))
+}
+
 /// Adds a new relay to the selector.
 ///
 /// The `relay_selector` crate contains hard-coded trust levels and vendor scores for relays. When
@@ -245,11 +452,16 @@ pub async fn get_relay(
 /// * `relay_url` - The URL of the relay to add.
 /// * `relay_type` - The type of relay. May be `"general"`, `"inbox"`, or `"outbox"`. Defaults to `"general"`.
 ///
+/// # Returns
+///
+/// `false` without adding the relay if it is on the server-side or client-side blocklist, `true`
+/// otherwise.
+///
 /// # Errors
 ///
 /// Throws an error if the relay type is invalid or if an error occurs while adding the relay.
 #[wasm_bindgen]
-pub async fn add_relay(relay_url: &str, relay_type: Option<String>) {
+pub async fn add_relay(relay_url: &str, relay_type: Option<String>) -> bool {
     let variant = match relay_type {
         Some(t) => relay::Variant::from_str(&t).unwrap_throw(),
         None => relay::Variant::General,
@@ -263,5 +475,168 @@ pub async fn add_relay(relay_url: &str, relay_type: Option<String>) {
         .as_mut()
         .unwrap_throw()
         .insert(relay_url, variant)
-        .await;
+        .await
+}
+
+/// Adds a new relay to the selector with explicit capability flags.
+///
+/// Otherwise identical to [`add_relay`], which is a thin wrapper over
+/// `RelaySelector::insert_with_usage` passing `RelayUsage::ALL` (every capability enabled).
+///
+/// # Arguments
+///
+/// * `relay_url` - The URL of the relay to add.
+/// * `relay_type` - The type of relay. May be `"general"`, `"inbox"`, or `"outbox"`. Defaults to `"general"`.
+/// * `read` - Whether the relay may be read from. Defaults to `true`.
+/// * `write` - Whether the relay may be written to. Defaults to `true`.
+/// * `advertise` - Whether the relay should be included in a published relay list. Defaults to `true`.
+///
+/// # Returns
+///
+/// `false` without adding the relay if it is on the server-side or client-side blocklist, `true`
+/// otherwise.
+///
+/// # Errors
+///
+/// Throws an error if the relay type is invalid or if an error occurs while adding the relay.
+#[wasm_bindgen]
+pub async fn add_relay_with_usage(
+    relay_url: &str,
+    relay_type: Option<String>,
+    read: Option<bool>,
+    write: Option<bool>,
+    advertise: Option<bool>,
+) -> bool {
+    let variant = match relay_type {
+        Some(t) => relay::Variant::from_str(&t).unwrap_throw(),
+        None => relay::Variant::General,
+    };
+    let usage = usage_from_optional_flags(read, write, advertise, relay::RelayUsage::ALL);
+
+    ensure_relay_selector_initialized(STORE_NAME).await;
+
+    let selector_rc = RELAY_SELECTOR.try_with(|rc| rc.clone()).unwrap_throw();
+    selector_rc
+        .borrow_mut()
+        .as_mut()
+        .unwrap_throw()
+        .insert_with_usage(relay_url, variant, usage)
+        .await
+}
+
+/// Records an author's declared relays from a kind-10002 relay-list event.
+///
+/// This powers the gossip/outbox-model relay routing used by [`get_write_relays_for_author`] and
+/// [`get_read_relays_for_author`].
+///
+/// # Arguments
+///
+/// * `pubkey` - The author's public key.
+/// * `tags` - The event's tags, as a JS array of string arrays (the standard Nostr tag shape).
+/// * `created_at` - The event's `created_at` Unix timestamp, used to detect a stale list later.
+#[wasm_bindgen]
+pub async fn update_author_relays(pubkey: &str, tags: JsValue, created_at: f64) {
+    let tags: Vec<Vec<String>> = serde_wasm_bindgen::from_value(tags).unwrap_throw();
+    let author_relays = relay::AuthorRelays::from_relay_list_tags(&tags, created_at as u64);
+
+    ensure_relay_selector_initialized(STORE_NAME).await;
+
+    let selector_rc = RELAY_SELECTOR.try_with(|rc| rc.clone()).unwrap_throw();
+    selector_rc
+        .borrow_mut()
+        .as_mut()
+        .unwrap_throw()
+        .update_author_relays(pubkey, author_relays);
+}
+
+/// Returns the Unix timestamp (seconds) of the relay-list event last recorded for `pubkey` via
+/// [`update_author_relays`], or `undefined` if no relay list is known for them yet, so a caller
+/// can decide whether it's time to refetch a stale list.
+#[wasm_bindgen]
+pub async fn get_author_relays_last_fetched(pubkey: &str) -> Option<f64> {
+    ensure_relay_selector_initialized(STORE_NAME).await;
+
+    let selector_rc = RELAY_SELECTOR.try_with(|rc| rc.clone()).unwrap_throw();
+    let last_fetched = selector_rc
+        .borrow()
+        .as_ref()
+        .unwrap_throw()
+        .author_relays_last_fetched(pubkey);
+
+    last_fetched.map(|timestamp| timestamp as f64)
+}
+
+/// Gets up to `max` relays where `pubkey` publishes their own content.
+///
+/// Prefer this over a global relay list when fetching a specific author's content: it returns
+/// the relays they actually write to, per their kind-10002 relay-list event (see
+/// [`update_author_relays`]), ranked by the selector's own statistics.
+#[wasm_bindgen]
+pub async fn get_write_relays_for_author(pubkey: &str, max: u32) -> Vec<JsValue> {
+    ensure_relay_selector_initialized(STORE_NAME).await;
+
+    let selector_rc = RELAY_SELECTOR.try_with(|rc| rc.clone()).unwrap_throw();
+    let relays = selector_rc
+        .borrow()
+        .as_ref()
+        .unwrap_throw()
+        .get_write_relays_for_author(pubkey, max as usize);
+
+    relays.into_iter().map(JsValue::from).collect()
+}
+
+/// Gets up to `max` relays where `pubkey` reads.
+///
+/// Prefer this over a global relay list when sending to a specific recipient: it returns the
+/// relays they actually monitor, per their kind-10002 relay-list event (see
+/// [`update_author_relays`]), ranked by the selector's own statistics.
+#[wasm_bindgen]
+pub async fn get_read_relays_for_author(pubkey: &str, max: u32) -> Vec<JsValue> {
+    ensure_relay_selector_initialized(STORE_NAME).await;
+
+    let selector_rc = RELAY_SELECTOR.try_with(|rc| rc.clone()).unwrap_throw();
+    let relays = selector_rc
+        .borrow()
+        .as_ref()
+        .unwrap_throw()
+        .get_read_relays_for_author(pubkey, max as usize);
+
+    relays.into_iter().map(JsValue::from).collect()
+}
+
+/// Sets a manual rank boost for a relay, nudging its computed weight without waiting for
+/// statistics to converge.
+///
+/// # Arguments
+///
+/// * `relay_url` - The relay URL.
+/// * `rank` - The manual rank boost. Replaces any existing value.
+#[wasm_bindgen]
+pub async fn rank_relay(relay_url: &str, rank: u8) {
+    ensure_relay_selector_initialized(STORE_NAME).await;
+
+    let selector_rc = RELAY_SELECTOR.try_with(|rc| rc.clone()).unwrap_throw();
+    selector_rc
+        .borrow_mut()
+        .as_mut()
+        .unwrap_throw()
+        .rank_relay(relay_url, rank);
+}
+
+/// Pins a relay to the front of its list, ahead of every unpinned relay regardless of computed
+/// weight.
+///
+/// # Arguments
+///
+/// * `relay_url` - The relay URL to pin.
+#[wasm_bindgen]
+pub async fn pin_relay(relay_url: &str) {
+    ensure_relay_selector_initialized(STORE_NAME).await;
+
+    let selector_rc = RELAY_SELECTOR.try_with(|rc| rc.clone()).unwrap_throw();
+    selector_rc
+        .borrow_mut()
+        .as_mut()
+        .unwrap_throw()
+        .pin_relay(relay_url);
 }
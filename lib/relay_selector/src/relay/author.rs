@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+/// The relays a given author has declared for reading and writing, per NIP-65 (kind 10002)
+/// relay-list events, i.e. the "gossip"/"outbox" model.
+#[derive(Clone, Debug, Default)]
+pub struct AuthorRelays {
+    /// Relays the author publishes their own content to. Callers wanting to fetch the author's
+    /// content should read from here.
+    pub write: Vec<String>,
+    /// Relays the author reads from. Callers wanting to reach the author (e.g. with a mention or
+    /// DM) should send here.
+    pub read: Vec<String>,
+    /// Unix timestamp (seconds) of the relay-list event this was parsed from, so a stale list can
+    /// be detected and refreshed.
+    pub last_fetched: u64,
+}
+
+impl AuthorRelays {
+    /// Parses an author's declared relays from the `r` tags of a kind-10002 relay-list event.
+    ///
+    /// Each tag is expected in the NIP-65 shape `["r", "<relay-url>", "read" | "write"]`. A tag
+    /// with no third element declares the relay for both reading and writing.
+    ///
+    /// # Arguments
+    ///
+    /// * `tags` - The event's tags, as an array of string arrays.
+    /// * `created_at` - The event's `created_at` timestamp, used as `last_fetched`.
+    pub fn from_relay_list_tags(tags: &[Vec<String>], created_at: u64) -> Self {
+        let mut relays = Self {
+            last_fetched: created_at,
+            ..Self::default()
+        };
+
+        for tag in tags {
+            if tag.first().map(String::as_str) != Some("r") {
+                continue;
+            }
+            let Some(url) = tag.get(1) else {
+                continue;
+            };
+
+            match tag.get(2).map(String::as_str) {
+                Some("read") => relays.read.push(url.clone()),
+                Some("write") => relays.write.push(url.clone()),
+                _ => {
+                    relays.read.push(url.clone());
+                    relays.write.push(url.clone());
+                }
+            }
+        }
+
+        relays
+    }
+}
+
+/// Maps an author's pubkey to their declared relays.
+pub type AuthorRelayMap = HashMap<String, AuthorRelays>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn untagged_relay_declares_both_read_and_write() {
+        let tags = vec![tag(&["r", "wss://relay.example"])];
+        let relays = AuthorRelays::from_relay_list_tags(&tags, 1000);
+
+        assert_eq!(relays.read, vec!["wss://relay.example"]);
+        assert_eq!(relays.write, vec!["wss://relay.example"]);
+        assert_eq!(relays.last_fetched, 1000);
+    }
+
+    #[test]
+    fn read_and_write_tags_are_kept_separate() {
+        let tags = vec![
+            tag(&["r", "wss://read.example", "read"]),
+            tag(&["r", "wss://write.example", "write"]),
+        ];
+        let relays = AuthorRelays::from_relay_list_tags(&tags, 1000);
+
+        assert_eq!(relays.read, vec!["wss://read.example"]);
+        assert_eq!(relays.write, vec!["wss://write.example"]);
+    }
+
+    #[test]
+    fn non_r_tags_are_ignored() {
+        let tags = vec![tag(&["p", "some-pubkey"])];
+        let relays = AuthorRelays::from_relay_list_tags(&tags, 1000);
+
+        assert!(relays.read.is_empty());
+        assert!(relays.write.is_empty());
+    }
+}
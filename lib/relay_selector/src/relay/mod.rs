@@ -1,7 +1,11 @@
+mod author;
 mod handle;
 mod statistics;
+mod usage;
 mod variant;
 
+pub use author::{AuthorRelayMap, AuthorRelays};
 pub use handle::RelayHandle;
 pub use statistics::Statistics;
+pub use usage::RelayUsage;
 pub use variant::Variant;
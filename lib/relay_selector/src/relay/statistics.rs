@@ -5,9 +5,16 @@ use crate::weights;
 pub struct Statistics {
     pub requests: u32,
     pub successful_requests: u32,
-    pub response_times: Vec<Duration>,
+    /// Exponentially-weighted moving average response time, in milliseconds. `None` until the
+    /// first sample is recorded.
+    pub ewma_ms: Option<f32>,
+    /// The number of response time samples folded into `ewma_ms` so far.
+    pub samples: u32,
     pub trust_level: f32,
     pub vendor_score: f32,
+    /// Manual rank boost set by an operator, e.g. via `RelaySelector::rank_relay`. Added directly
+    /// to the computed weight alongside `trust_level` and `vendor_score`.
+    pub rank: f32,
     active_connections: u8,
 }
 
@@ -16,9 +23,11 @@ impl Statistics {
         Self {
             requests: 0,
             successful_requests: 0,
-            response_times: Vec::new(),
+            ewma_ms: None,
+            samples: 0,
             trust_level: 0.0,
             vendor_score: 0.0,
+            rank: 0.0,
             active_connections: 0,
         }
     }
@@ -27,6 +36,9 @@ impl Statistics {
 impl Statistics {
     /// Adds a response time datum returns updated weights.
     ///
+    /// Folds the sample into the bounded, recency-weighted `ewma_ms` average instead of growing
+    /// an unbounded history, so this is O(1) regardless of how many samples a relay has seen.
+    ///
     /// # Arguments
     ///
     /// * `response_time` - The time it took for the request to complete.
@@ -35,13 +47,16 @@ impl Statistics {
     ///
     /// A tuple containing the updated initial and current weights of the relay.
     pub fn add_response_time(&mut self, response_time: Duration) -> (f32, f32) {
-        self.response_times.push(response_time);
+        let sample_ms = response_time.as_secs_f32() * 1000.0;
+        self.ewma_ms = Some(weights::update_ewma(self.ewma_ms, sample_ms));
+        self.samples += 1;
         weights::calculate_weights(
-            self.response_times.as_mut_slice(),
+            self.ewma_ms,
             self.successful_requests,
             self.requests,
             self.trust_level,
             self.vendor_score,
+            self.rank,
             self.active_connections,
         )
     }
@@ -61,11 +76,12 @@ impl Statistics {
             self.successful_requests += 1;
         }
         weights::calculate_weights(
-            self.response_times.as_mut_slice(),
+            self.ewma_ms,
             self.successful_requests,
             self.requests,
             self.trust_level,
             self.vendor_score,
+            self.rank,
             self.active_connections,
         )
     }
@@ -78,11 +94,12 @@ impl Statistics {
     pub fn add_active_connection(&mut self) -> (f32, f32) {
         self.active_connections += 1;
         weights::calculate_weights(
-            self.response_times.as_mut_slice(),
+            self.ewma_ms,
             self.successful_requests,
             self.requests,
             self.trust_level,
             self.vendor_score,
+            self.rank,
             self.active_connections,
         )
     }
@@ -95,11 +112,34 @@ impl Statistics {
     pub fn remove_active_connection(&mut self) -> (f32, f32) {
         self.active_connections -= 1;
         weights::calculate_weights(
-            self.response_times.as_mut_slice(),
+            self.ewma_ms,
+            self.successful_requests,
+            self.requests,
+            self.trust_level,
+            self.vendor_score,
+            self.rank,
+            self.active_connections,
+        )
+    }
+
+    /// Sets the manual rank boost, replacing the existing one, then returns updated weights.
+    ///
+    /// # Arguments
+    ///
+    /// * `rank` - The new rank boost.
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing the updated initial and current weights of the relay.
+    pub fn update_rank(&mut self, rank: f32) -> (f32, f32) {
+        self.rank = rank;
+        weights::calculate_weights(
+            self.ewma_ms,
             self.successful_requests,
             self.requests,
             self.trust_level,
             self.vendor_score,
+            self.rank,
             self.active_connections,
         )
     }
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+/// Per-relay capability flags, orthogonal to which list(s) a relay is bucketed into.
+///
+/// A relay's [`super::Variant`] determines which selector list it's ranked in; `RelayUsage`
+/// separately tracks what the relay may actually be used for. A relay can be read-only, write-
+/// only, both, and independently flagged `advertise` (included in a published relay list),
+/// without needing to be duplicated across lists to represent each role.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RelayUsage {
+    pub read: bool,
+    pub write: bool,
+    pub advertise: bool,
+}
+
+impl RelayUsage {
+    /// No capability required. Used as the "no filter" sentinel in selection queries that don't
+    /// care about capability, e.g. [`super::super::relay_selector::RelaySelector::get_relay_by_weighted_round_robin`].
+    pub const NONE: Self = Self {
+        read: false,
+        write: false,
+        advertise: false,
+    };
+
+    /// Every capability. The default assumed for a relay added without explicit flags, and for
+    /// persisted records written before this flag set existed.
+    pub const ALL: Self = Self {
+        read: true,
+        write: true,
+        advertise: true,
+    };
+
+    /// Returns `true` if this relay's capabilities satisfy every capability set in `required`.
+    ///
+    /// A flag unset in `required` is not checked, so `RelayUsage::NONE` is satisfied by any
+    /// relay.
+    pub fn satisfies(&self, required: RelayUsage) -> bool {
+        (!required.read || self.read)
+            && (!required.write || self.write)
+            && (!required.advertise || self.advertise)
+    }
+}
+
+impl Default for RelayUsage {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn none_is_satisfied_by_any_relay() {
+        let read_only = RelayUsage {
+            read: true,
+            write: false,
+            advertise: false,
+        };
+        assert!(read_only.satisfies(RelayUsage::NONE));
+    }
+
+    #[test]
+    fn write_only_relay_does_not_satisfy_read_requirement() {
+        let write_only = RelayUsage {
+            read: false,
+            write: true,
+            advertise: false,
+        };
+        let require_read = RelayUsage {
+            read: true,
+            write: false,
+            advertise: false,
+        };
+        assert!(!write_only.satisfies(require_read));
+    }
+
+    #[test]
+    fn all_satisfies_every_requirement() {
+        assert!(RelayUsage::ALL.satisfies(RelayUsage::ALL));
+    }
+}
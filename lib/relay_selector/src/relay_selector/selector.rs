@@ -1,7 +1,9 @@
 use futures::executor::LocalPool;
 use futures::task::LocalSpawnExt;
+use std::cell::RefCell;
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 use std::time::Duration;
 use wasm_bindgen::UnwrapThrowExt;
 
@@ -25,54 +27,51 @@ pub struct RelaySelector {
     inbox: Vec<String>,
     outbox: Vec<String>,
 
-    store_name: String,
+    // Per-relay capability flags (read/write/advertise), keyed by (url, variant) rather than url
+    // alone, since the same relay URL commonly appears in more than one variant bucket (e.g. both
+    // `inbox` and `outbox`) and can play a different role in each.
+    pub usage: HashMap<(String, relay::Variant), relay::RelayUsage>,
+
+    // Relays manually pinned to the front of their list via `pin_relay`, regardless of weight.
+    pub pinned: HashSet<String>,
+
+    // Per-author relay routing (NIP-65 gossip/outbox model), keyed by pubkey.
+    author_relays: relay::AuthorRelayMap,
+
+    // Persistence backend. Boxed behind `RelayRepository` so the selector can run against
+    // IndexedDB, an in-memory store, or any other backend without changing selection logic.
+    repository: Rc<dyn database::RelayRepository>,
+
+    // Configuration source (trust levels, vendor scores, allow/block lists). Boxed behind
+    // `ConfigProvider` so the selector doesn't care whether it's backed by a JS callback or
+    // another source, and stored once at construction so every config-dependent call site shares
+    // the same provider instead of each needing one passed in separately.
+    config_provider: Rc<dyn config::ConfigProvider>,
+
+    // Write-behind cache: relays mutated since the last flush, and the policy used to flush them.
+    // Interior mutability lets `flush`/`flush_dirty` take `&self`, so callers can checkpoint
+    // without needing a mutable borrow of the selector.
+    dirty: RefCell<HashSet<String>>,
+    cache_update_policy: database::CacheUpdatePolicy,
 }
 
 impl Drop for RelaySelector {
     fn drop(&mut self) {
-        let relays: Vec<database::Relay> = self
-            .general
-            .iter()
-            .map(|url| {
-                database::Relay::from_repositories(
-                    url,
-                    relay::Variant::General,
-                    &self.statistics[url],
-                    self,
-                )
-            })
-            .chain(self.inbox.iter().map(|url| {
-                database::Relay::from_repositories(
-                    url,
-                    relay::Variant::Inbox,
-                    &self.statistics[url],
-                    self,
-                )
-            }))
-            .chain(self.outbox.iter().map(|url| {
-                database::Relay::from_repositories(
-                    url,
-                    relay::Variant::Outbox,
-                    &self.statistics[url],
-                    self,
-                )
-            }))
-            .collect();
+        let relays = self.snapshot();
 
-        let store_name = self.store_name.clone();
+        let repository = Rc::clone(&self.repository);
         LocalPool::new()
             .spawner()
-            .spawn_local(async move {
-                database::insert_or_update(&store_name, relays.as_slice())
-                    .await
-                    .unwrap_throw()
-            })
+            .spawn_local(async move { repository.persist(relays).await.unwrap_throw() })
             .unwrap_throw()
     }
 }
 
 impl RelaySelector {
-    pub fn new() -> Self {
+    pub fn new(
+        repository: Rc<dyn database::RelayRepository>,
+        config_provider: Rc<dyn config::ConfigProvider>,
+    ) -> Self {
         Self {
             statistics: HashMap::new(),
             initial_weights: HashMap::new(),
@@ -80,16 +79,70 @@ impl RelaySelector {
             general: Vec::new(),
             inbox: Vec::new(),
             outbox: Vec::new(),
-            store_name: String::new(),
+            usage: HashMap::new(),
+            pinned: HashSet::new(),
+            author_relays: HashMap::new(),
+            repository,
+            config_provider,
+            dirty: RefCell::new(HashSet::new()),
+            cache_update_policy: database::CacheUpdatePolicy::default(),
         }
     }
 
-    /// Initializes the relay selector with data from the IndexedDB store with the given name.
-    pub async fn init(store_name: &str) -> Result<Self, String> {
-        let mut selector = Self::new();
+    /// Sets the policy used by [`RelaySelector::flush_dirty`] to write out dirty relays.
+    pub fn set_cache_update_policy(&mut self, policy: database::CacheUpdatePolicy) {
+        self.cache_update_policy = policy;
+    }
+
+    /// Marks a relay's in-memory state as dirty, so the next [`RelaySelector::flush_dirty`] call
+    /// picks it up.
+    fn mark_dirty(&self, relay: &str) {
+        self.dirty.borrow_mut().insert(relay.to_string());
+    }
+
+    /// Returns every variant bucket a known relay currently belongs to.
+    ///
+    /// A relay commonly belongs to more than one bucket at once — `populate_defaults` puts every
+    /// default relay into `general`, `inbox`, and `outbox` simultaneously — so callers that need
+    /// to touch *all* of a relay's buckets (re-sorting after a weight change, or building one
+    /// persisted row per variant membership) must use this instead of assuming a single variant.
+    fn variants_of(&self, relay: &str) -> Vec<relay::Variant> {
+        let mut variants = Vec::new();
+        if self.general.contains(&relay.to_string()) {
+            variants.push(relay::Variant::General);
+        }
+        if self.inbox.contains(&relay.to_string()) {
+            variants.push(relay::Variant::Inbox);
+        }
+        if self.outbox.contains(&relay.to_string()) {
+            variants.push(relay::Variant::Outbox);
+        }
+        variants
+    }
 
-        for relay in database::get_all_relays(store_name).await? {
-            selector.insert(&relay.url, relay.variant).await;
+    /// Initializes the relay selector with data loaded from the given persistence backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The persistence backend to load from and persist to. Pass
+    ///   [`database::IndexedDbRepository`] in a browser context, or
+    ///   [`database::InMemoryRepository`] (or another implementation) where IndexedDB is
+    ///   unavailable, e.g. under Node or server-side rendering.
+    /// * `config_provider` - The configuration source used for trust levels, vendor scores, and
+    ///   allow/block lists. See [`config::ConfigProvider`].
+    pub async fn init(
+        repository: Rc<dyn database::RelayRepository>,
+        config_provider: Rc<dyn config::ConfigProvider>,
+    ) -> Result<Self, String> {
+        let mut selector = Self::new(Rc::clone(&repository), config_provider);
+
+        for relay in repository.load_all().await? {
+            if relay.pinned {
+                selector.pinned.insert(relay.url.clone());
+            }
+            selector
+                .insert_with_usage(&relay.url, relay.variant, relay.usage)
+                .await;
             selector
                 .statistics
                 .insert(relay.url.clone(), relay.to_statistics());
@@ -99,7 +152,6 @@ impl RelaySelector {
             selector
                 .current_weights
                 .insert(relay.url.clone(), relay.weight);
-            selector.store_name = store_name.to_string();
         }
 
         // Add defaults if lists are empty
@@ -110,6 +162,100 @@ impl RelaySelector {
         Ok(selector)
     }
 
+    /// Builds a database record for every known relay from current in-memory statistics and
+    /// weights, ready to hand to the persistence backend.
+    fn snapshot(&self) -> Vec<database::Relay> {
+        self.general
+            .iter()
+            .map(|url| {
+                database::Relay::from_repositories(
+                    url,
+                    relay::Variant::General,
+                    &self.statistics[url],
+                    self,
+                )
+            })
+            .chain(self.inbox.iter().map(|url| {
+                database::Relay::from_repositories(
+                    url,
+                    relay::Variant::Inbox,
+                    &self.statistics[url],
+                    self,
+                )
+            }))
+            .chain(self.outbox.iter().map(|url| {
+                database::Relay::from_repositories(
+                    url,
+                    relay::Variant::Outbox,
+                    &self.statistics[url],
+                    self,
+                )
+            }))
+            .collect()
+    }
+
+    /// Persists every known relay to the configured repository immediately, regardless of dirty
+    /// state, and clears the dirty set.
+    ///
+    /// Unlike the best-effort flush performed in `Drop`, this surfaces persistence errors to the
+    /// caller. Prefer [`RelaySelector::flush_dirty`] for routine checkpoints, since it only writes
+    /// what actually changed; use this to force a full resync, e.g. after switching backends.
+    pub async fn flush(&self) -> Result<(), String> {
+        self.repository.persist(self.snapshot()).await?;
+        self.dirty.borrow_mut().clear();
+        Ok(())
+    }
+
+    /// Flushes the relays marked dirty since the last flush according to the selector's
+    /// [`database::CacheUpdatePolicy`] (see [`RelaySelector::set_cache_update_policy`]), and
+    /// clears them from the dirty set on success. Does nothing when no relay is dirty; under
+    /// `Overwrite`, every known relay is persisted, not only the dirty ones.
+    ///
+    /// This is the preferred way for a caller to checkpoint multiple statistics updates (e.g. a
+    /// batch of telemetry) with a single transaction instead of relying on the best-effort flush
+    /// performed in `Drop`, which silently loses all updates if the process ends abnormally.
+    pub async fn flush_dirty(&self) -> Result<(), String> {
+        let dirty: Vec<String> = self.dirty.borrow().iter().cloned().collect();
+        if dirty.is_empty() {
+            return Ok(());
+        }
+
+        match self.cache_update_policy {
+            database::CacheUpdatePolicy::WriteThrough => {
+                // A dirty URL may belong to more than one variant bucket at once, each with its
+                // own persisted row (and its own `usage` flags) — emit a row for every bucket it's
+                // actually in, not just one guessed variant.
+                let mut relays = Vec::new();
+                for url in &dirty {
+                    let Some(statistics) = self.statistics.get(url) else {
+                        continue;
+                    };
+                    for variant in self.variants_of(url) {
+                        relays.push(database::Relay::from_repositories(
+                            url, variant, statistics, self,
+                        ));
+                    }
+                }
+                self.repository.persist(relays).await?;
+            }
+            database::CacheUpdatePolicy::Overwrite => {
+                self.repository.persist(self.snapshot()).await?;
+            }
+            database::CacheUpdatePolicy::Remove => {
+                let mut keys = Vec::new();
+                for url in &dirty {
+                    for variant in self.variants_of(url) {
+                        keys.push(database::Relay::storage_key(url, variant));
+                    }
+                }
+                self.repository.remove(keys).await?;
+            }
+        }
+
+        self.dirty.borrow_mut().clear();
+        Ok(())
+    }
+
     /// Populates the selector with default relays for empty variant lists.
     async fn populate_defaults(&mut self) -> Result<(), String> {
         // Add default general relays if list is empty
@@ -144,8 +290,60 @@ impl RelaySelector {
             || self.outbox.contains(&relay.to_string())
     }
 
-    /// Inserts a relay into the selector, respecting its type (i.e., its intended usage category).
-    pub async fn insert(&mut self, relay: &str, variant: relay::Variant) {
+    /// Returns `true` if the given relay URL is on the server-side or client-side blocklist.
+    ///
+    /// Blocked relays are rejected outright in [`RelaySelector::insert`] and filtered out of
+    /// [`RelaySelector::get_relay_by_weighted_round_robin`], in every mode, regardless of
+    /// `is_server_side`. This gives operators a hard override on top of the soft weighting: a
+    /// blocked relay can never be returned no matter how much weight it has accumulated.
+    async fn is_blocked(&self, relay: &str) -> bool {
+        let is_server_blocked = config::get_server_side_relay_block_list(
+            self.config_provider.as_ref(),
+        )
+        .await
+        .map(|block_list| block_list.iter().any(|blocked| blocked == relay))
+        .unwrap_or(false);
+        let is_client_blocked = config::get_relay_block_list(self.config_provider.as_ref())
+            .await
+            .map(|block_list| block_list.iter().any(|blocked| blocked == relay))
+            .unwrap_or(false);
+        is_server_blocked || is_client_blocked
+    }
+
+    /// Inserts a relay into the selector, respecting its type (i.e., its intended usage category),
+    /// with every capability flag (read, write, advertise) enabled by default.
+    ///
+    /// # Returns
+    ///
+    /// `false` without inserting if the relay is on the server-side or client-side blocklist,
+    /// `true` otherwise.
+    pub async fn insert(&mut self, relay: &str, variant: relay::Variant) -> bool {
+        self.insert_with_usage(relay, variant, relay::RelayUsage::ALL)
+            .await
+    }
+
+    /// Inserts a relay into the selector with explicit capability flags, respecting its type
+    /// (i.e., its intended usage category).
+    ///
+    /// Real relay policy is orthogonal to the variant buckets above: a relay can be read-only,
+    /// write-only, both, or separately flagged `advertise`, without needing to be duplicated
+    /// across lists for each role. Selection queries that need a specific capability should use
+    /// [`RelaySelector::get_relay_by_capability`].
+    ///
+    /// # Returns
+    ///
+    /// `false` without inserting if the relay is on the server-side or client-side blocklist,
+    /// `true` otherwise.
+    pub async fn insert_with_usage(
+        &mut self,
+        relay: &str,
+        variant: relay::Variant,
+        usage: relay::RelayUsage,
+    ) -> bool {
+        if self.is_blocked(relay).await {
+            return false;
+        }
+
         // Add the relay to the appropriate collections based on its variant.
         match variant {
             relay::Variant::General => self.general.push(relay.to_string()),
@@ -154,6 +352,8 @@ impl RelaySelector {
             _ => self.general.push(relay.to_string()),
         }
 
+        self.usage.insert((relay.to_string(), variant), usage);
+
         // Set up the relay's representation in the selector with initial defaults.
         self.statistics
             .insert(relay.to_string(), relay::Statistics::new());
@@ -163,28 +363,37 @@ impl RelaySelector {
             .insert(relay.to_string(), defaults::DEFAULT_WEIGHT);
 
         // If any trust level or vendor score is configured, update the weights accordingly.
-        let trust_level = config::get_trust_level(relay);
-        let vendor_score = config::get_vendor_score(relay);
-        self.update_weights_with_trust_level(relay, trust_level.await as f32);
-        self.update_weights_with_vendor_score(relay, vendor_score.await as f32);
+        let trust_level = config::get_trust_level(self.config_provider.as_ref(), relay).await;
+        let vendor_score = config::get_vendor_score(self.config_provider.as_ref(), relay).await;
+        self.update_weights_with_trust_level(relay, trust_level as f32);
+        self.update_weights_with_vendor_score(relay, vendor_score as f32);
 
         // Sort the relay collections based on the weights.
+        self.resort(variant);
+
+        true
+    }
+
+    fn get_mut_statistics(&mut self, relay: &str) -> &mut relay::Statistics {
+        self.statistics.get_mut(relay).unwrap()
+    }
+
+    /// Re-sorts the given variant's list by current weight, keeping pinned relays in front.
+    fn resort(&mut self, variant: relay::Variant) {
         match variant {
             relay::Variant::General => {
-                weights::weighted_sort(&mut self.general, &self.current_weights)
+                weights::pinned_weighted_sort(&mut self.general, &self.current_weights, &self.pinned)
+            }
+            relay::Variant::Inbox => {
+                weights::pinned_weighted_sort(&mut self.inbox, &self.current_weights, &self.pinned)
             }
-            relay::Variant::Inbox => weights::weighted_sort(&mut self.inbox, &self.current_weights),
             relay::Variant::Outbox => {
-                weights::weighted_sort(&mut self.outbox, &self.current_weights)
+                weights::pinned_weighted_sort(&mut self.outbox, &self.current_weights, &self.pinned)
             }
             _ => (),
         }
     }
 
-    fn get_mut_statistics(&mut self, relay: &str) -> &mut relay::Statistics {
-        self.statistics.get_mut(relay).unwrap()
-    }
-
     /// Updates relay weights based on a new response time datum.
     ///
     /// # Arguments
@@ -200,6 +409,7 @@ impl RelaySelector {
             .insert(relay.to_string(), initial_weight);
         self.current_weights
             .insert(relay.to_string(), current_weight);
+        self.mark_dirty(relay);
     }
 
     /// Updates relay weights based on a new completed request.
@@ -215,6 +425,7 @@ impl RelaySelector {
             .insert(relay.to_string(), initial_weight);
         self.current_weights
             .insert(relay.to_string(), current_weight);
+        self.mark_dirty(relay);
     }
 
     /// Updates the trust level of a relay, then updates its weights accordingly.
@@ -232,6 +443,7 @@ impl RelaySelector {
             .insert(relay.to_string(), initial_weight);
         self.current_weights
             .insert(relay.to_string(), current_weight);
+        self.mark_dirty(relay);
     }
 
     /// Updates the vendor score of a relay, then updates its weights accordingly.
@@ -249,6 +461,62 @@ impl RelaySelector {
             .insert(relay.to_string(), initial_weight);
         self.current_weights
             .insert(relay.to_string(), current_weight);
+        self.mark_dirty(relay);
+    }
+
+    /// Sets a manual rank boost for a relay, added directly to its computed weight alongside
+    /// `trust_level` and `vendor_score` (see [`weights::calculate_weights`]). Gives an operator a
+    /// way to nudge a relay up or down without waiting for statistics to converge.
+    ///
+    /// Does nothing if the selector doesn't know about `relay` yet, e.g. a UI ranking a relay
+    /// before it's been discovered or added.
+    ///
+    /// # Arguments
+    ///
+    /// * `relay` - The relay URL.
+    /// * `rank` - The manual rank boost. Replaces any existing value.
+    pub fn rank_relay(&mut self, relay: &str, rank: u8) {
+        if !self.contains(relay) {
+            return;
+        }
+
+        let (initial_weight, current_weight) =
+            self.get_mut_statistics(relay).update_rank(rank as f32);
+
+        self.initial_weights
+            .insert(relay.to_string(), initial_weight);
+        self.current_weights
+            .insert(relay.to_string(), current_weight);
+        self.mark_dirty(relay);
+
+        // A relay commonly belongs to more than one variant bucket at once; re-sort every one it
+        // belongs to, not just one guessed variant, or the others would silently keep stale order.
+        for variant in self.variants_of(relay) {
+            self.resort(variant);
+        }
+    }
+
+    /// Pins a relay to the front of its list, ahead of every unpinned relay regardless of
+    /// computed weight. Gives an operator a way to guarantee a trusted relay is always tried
+    /// first.
+    ///
+    /// Does nothing if the selector doesn't know about `relay` yet, e.g. a UI pinning a relay
+    /// before it's been discovered or added.
+    ///
+    /// # Arguments
+    ///
+    /// * `relay` - The relay URL to pin.
+    pub fn pin_relay(&mut self, relay: &str) {
+        if !self.contains(relay) {
+            return;
+        }
+
+        self.pinned.insert(relay.to_string());
+        self.mark_dirty(relay);
+
+        for variant in self.variants_of(relay) {
+            self.resort(variant);
+        }
     }
 
     /// Selects a relay based on weighted round-robin algorithm.
@@ -275,6 +543,34 @@ impl RelaySelector {
         variant: relay::Variant,
         rank: usize,
         is_server_side: bool,
+    ) -> Result<String, String> {
+        self.get_relay_by_capability(variant, relay::RelayUsage::NONE, rank, is_server_side)
+            .await
+    }
+
+    /// Selects a relay based on weighted round-robin algorithm, restricted to relays whose
+    /// capability flags satisfy `required` (e.g. "give me the top write relay").
+    ///
+    /// Otherwise identical to [`RelaySelector::get_relay_by_weighted_round_robin`], which is a
+    /// thin wrapper over this method passing `RelayUsage::NONE` (no capability requirement).
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The desired relay variant.
+    /// * `required` - The capability flags the selected relay must have.
+    /// * `rank` - The desired relay rank.
+    /// * `is_server_side` - Whether the call is coming from server-side code.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` - The selected relay.
+    /// * `Err(String)` - An error message.
+    pub async fn get_relay_by_capability(
+        &mut self,
+        variant: relay::Variant,
+        required: relay::RelayUsage,
+        rank: usize,
+        is_server_side: bool,
     ) -> Result<String, String> {
         let ranked = match variant {
             relay::Variant::General => &self.general,
@@ -288,9 +584,28 @@ impl RelaySelector {
             }
         };
 
+        // Filter out blocked relays and relays missing a required capability before ranking, so
+        // neither can ever be returned regardless of accumulated weight, in every mode (not just
+        // server-side).
+        let mut candidates = Vec::new();
+        for relay in ranked.iter() {
+            if self.is_blocked(relay).await {
+                continue;
+            }
+            let usage = self
+                .usage
+                .get(&(relay.clone(), variant))
+                .copied()
+                .unwrap_or(relay::RelayUsage::ALL);
+            if !usage.satisfies(required) {
+                continue;
+            }
+            candidates.push(relay.clone());
+        }
+
         // Grab the relay of the requested rank
         // Assumes relays are sorted in descending order of rank
-        let selected = ranked
+        let selected = candidates
             .get(rank)
             .ok_or(format!(
                 "[RelaySelector] No {:?} relay found at rank {:?}",
@@ -298,7 +613,7 @@ impl RelaySelector {
             ))?
             .clone();
 
-        let is_allowed = config::get_server_side_relay_allow_list()
+        let is_allowed = config::get_server_side_relay_allow_list(self.config_provider.as_ref())
             .await
             .and_then(|allow_list| {
                 Ok(allow_list
@@ -320,14 +635,17 @@ impl RelaySelector {
             .insert(selected.clone(), initial_weight);
         self.current_weights
             .insert(selected.clone(), current_weight);
+        self.mark_dirty(&selected);
 
         match variant {
             relay::Variant::General => {
-                weights::weighted_sort(&mut self.general, &self.current_weights)
+                weights::pinned_weighted_sort(&mut self.general, &self.current_weights, &self.pinned)
+            }
+            relay::Variant::Inbox => {
+                weights::pinned_weighted_sort(&mut self.inbox, &self.current_weights, &self.pinned)
             }
-            relay::Variant::Inbox => weights::weighted_sort(&mut self.inbox, &self.current_weights),
             relay::Variant::Outbox => {
-                weights::weighted_sort(&mut self.outbox, &self.current_weights)
+                weights::pinned_weighted_sort(&mut self.outbox, &self.current_weights, &self.pinned)
             }
             _ => (),
         }
@@ -354,16 +672,92 @@ impl RelaySelector {
             .insert(relay.to_string(), initial_weight);
         self.current_weights
             .insert(relay.to_string(), current_weight);
+        self.mark_dirty(relay);
 
         match variant {
             relay::Variant::General => {
-                weights::weighted_sort(&mut self.general, &self.current_weights)
+                weights::pinned_weighted_sort(&mut self.general, &self.current_weights, &self.pinned)
+            }
+            relay::Variant::Inbox => {
+                weights::pinned_weighted_sort(&mut self.inbox, &self.current_weights, &self.pinned)
             }
-            relay::Variant::Inbox => weights::weighted_sort(&mut self.inbox, &self.current_weights),
             relay::Variant::Outbox => {
-                weights::weighted_sort(&mut self.outbox, &self.current_weights)
+                weights::pinned_weighted_sort(&mut self.outbox, &self.current_weights, &self.pinned)
             }
             _ => (),
         }
     }
+
+    /// Records or replaces the declared relays for the given author, parsed from their kind-10002
+    /// relay-list event.
+    ///
+    /// # Arguments
+    ///
+    /// * `pubkey` - The author's public key.
+    /// * `author_relays` - The author's declared relays, e.g. from
+    ///   [`relay::AuthorRelays::from_relay_list_tags`].
+    pub fn update_author_relays(&mut self, pubkey: &str, author_relays: relay::AuthorRelays) {
+        self.author_relays.insert(pubkey.to_string(), author_relays);
+    }
+
+    /// Returns the timestamp of the last relay-list event recorded for `pubkey`, or `None` if no
+    /// relay list is known for them, so callers can decide whether to refetch a stale list.
+    pub fn author_relays_last_fetched(&self, pubkey: &str) -> Option<u64> {
+        self.author_relays.get(pubkey).map(|relays| relays.last_fetched)
+    }
+
+    /// Returns up to `max` relays where `pubkey` publishes their own content, so callers can
+    /// fetch directly from the source instead of blasting a global relay list.
+    ///
+    /// Intersects the author's declared write relays (from their kind-10002 relay-list event)
+    /// with the selector's known relay statistics, ranked by [`weights::weighted_sort`]. Falls
+    /// back to the default `outbox` list when the author has no declared relays.
+    pub fn get_write_relays_for_author(&self, pubkey: &str, max: usize) -> Vec<String> {
+        self.get_relays_for_author(pubkey, max, |author| &author.write, &self.outbox)
+    }
+
+    /// Returns up to `max` relays where `pubkey` reads, so callers can reach them directly instead
+    /// of blasting a global relay list.
+    ///
+    /// Intersects the author's declared read relays (from their kind-10002 relay-list event) with
+    /// the selector's known relay statistics, ranked by [`weights::weighted_sort`]. Falls back to
+    /// the default `inbox` list when the author has no declared relays.
+    pub fn get_read_relays_for_author(&self, pubkey: &str, max: usize) -> Vec<String> {
+        self.get_relays_for_author(pubkey, max, |author| &author.read, &self.inbox)
+    }
+
+    /// Shared implementation for [`RelaySelector::get_write_relays_for_author`] and
+    /// [`RelaySelector::get_read_relays_for_author`].
+    fn get_relays_for_author(
+        &self,
+        pubkey: &str,
+        max: usize,
+        select: impl Fn(&relay::AuthorRelays) -> &Vec<String>,
+        fallback: &[String],
+    ) -> Vec<String> {
+        let declared = self
+            .author_relays
+            .get(pubkey)
+            .map(select)
+            .filter(|relays| !relays.is_empty());
+
+        let mut candidates: Vec<String> = match declared {
+            // Only rank relays the selector actually has statistics for; an author's declared
+            // relay may not be one the selector has ever connected to.
+            Some(relays) => relays
+                .iter()
+                .filter(|relay| self.current_weights.contains_key(relay.as_str()))
+                .cloned()
+                .collect(),
+            None => fallback.to_vec(),
+        };
+
+        if candidates.is_empty() {
+            return candidates;
+        }
+
+        weights::weighted_sort(&mut candidates, &self.current_weights);
+        candidates.truncate(max);
+        candidates
+    }
 }
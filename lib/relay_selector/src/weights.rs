@@ -1,10 +1,31 @@
-use std::collections::HashMap;
-use std::time::Duration;
+use std::collections::{HashMap, HashSet};
 
 /// The weight modifier applied to a relay when it is in use. Active relays are deprioritized in
 /// weighted round robin selections.
 pub const CONNECTION_WEIGHT: f32 = 0.1;
 
+/// The smoothing factor used to update a relay's exponentially-weighted moving average response
+/// time. Higher values give more weight to the most recent sample; lower values smooth out noise
+/// at the cost of reacting more slowly to real changes in latency.
+pub const EWMA_ALPHA: f32 = 0.2;
+
+/// Updates an exponentially-weighted moving average with a new sample.
+///
+/// # Arguments
+///
+/// * `ewma` - The current average, or `None` if no sample has been recorded yet.
+/// * `sample` - The new sample to fold in.
+///
+/// # Returns
+///
+/// The updated average. Initialized to `sample` when `ewma` is `None`.
+pub fn update_ewma(ewma: Option<f32>, sample: f32) -> f32 {
+    match ewma {
+        Some(prev) => EWMA_ALPHA * sample + (1.0 - EWMA_ALPHA) * prev,
+        None => sample,
+    }
+}
+
 pub type RelayWeights = HashMap<String, f32>;
 
 /// Given a list of relays and relay weights, sorts the relays in descending order of weight.
@@ -40,18 +61,44 @@ pub fn weighted_sort(relays: &mut Vec<String>, weights: &RelayWeights) {
     });
 }
 
+/// Sorts relays as [`weighted_sort`], except relays in `pinned` are kept ahead of every
+/// non-pinned relay regardless of computed weight. Relative order within each group is still
+/// determined by weight.
+///
+/// # Arguments
+///
+/// * `relays` - A mutable vector of relay URLs. This vector will be sorted in place.
+/// * `weights` - A reference to a map containing the weights of each relay.
+/// * `pinned` - The set of relay URLs to keep pinned to the front.
+pub fn pinned_weighted_sort(relays: &mut Vec<String>, weights: &RelayWeights, pinned: &HashSet<String>) {
+    let (mut front, mut rest): (Vec<String>, Vec<String>) =
+        relays.drain(..).partition(|relay| pinned.contains(relay));
+
+    if !front.is_empty() {
+        weighted_sort(&mut front, weights);
+    }
+    if !rest.is_empty() {
+        weighted_sort(&mut rest, weights);
+    }
+
+    front.append(&mut rest);
+    *relays = front;
+}
+
 /// Calculates weights for a relay based on its statistics.
 ///
 /// # Arguments
 ///
-/// * `response_times` - A mutable slice of durations representing the response times of the relay.
-///   The slice must be mutable so that it can be sorted in place.
+/// * `ewma_response_time_ms` - The relay's exponentially-weighted moving average response time,
+///   in milliseconds, or `None` if no response time has been recorded yet.
 /// * `successful_requests` - The number of successful requests made to the relay.
 /// * `total_requests` - The total number of requests made to the relay.
 /// * `trust_level_weight` - A modifier used to more strongly weight relays known to be
 ///   trustworthy.
 /// * `preferred_vendor_weight` - A modifier used to increase the weight of relays maintained by
 ///   preferred or partner vendors.
+/// * `rank_weight` - A manual rank boost set by an operator (see `RelaySelector::rank_relay`),
+///   added alongside `trust_level_weight` and `preferred_vendor_weight`.
 /// * `active_connections` - The number of currently active connections to the relay.
 ///
 /// # Returns
@@ -59,31 +106,82 @@ pub fn weighted_sort(relays: &mut Vec<String>, weights: &RelayWeights) {
 /// A tuple of the relay's initial weight (before accounting for active connections) and its
 /// current weight (adjusted for active connections).
 pub fn calculate_weights(
-    response_times: &mut [Duration],
+    ewma_response_time_ms: Option<f32>,
     successful_requests: u32,
     total_requests: u32,
     trust_level_weight: f32,
     preferred_vendor_weight: f32,
+    rank_weight: f32,
     active_connections: u8,
 ) -> (f32, f32) {
-    // Get the median response time in milliseconds
-    response_times.sort();
-    let response_times_len = response_times.len();
-    let is_odd_len = response_times_len % 2 == 1;
-    let median_time = if is_odd_len {
-        response_times[response_times_len / 2].as_millis() as f32
+    let response_time_weight = match ewma_response_time_ms {
+        Some(ms) if ms > 0.0 => -1.0 * ms.log10() + 1.0,
+        _ => 0.0,
+    };
+    let success_rate = if total_requests > 0 {
+        successful_requests as f32 / total_requests as f32
     } else {
-        (response_times[response_times_len / 2].as_millis() as f32
-            + response_times[response_times_len / 2 - 1].as_millis() as f32)
-            / 2f32
+        0.0
     };
 
-    let response_time_weight = -1.0 * median_time.log10() + 1.0;
-    let success_rate: i32 = successful_requests as i32 / total_requests as i32;
-
-    let initial_weight =
-        response_time_weight * success_rate as f32 + trust_level_weight + preferred_vendor_weight;
+    let initial_weight = response_time_weight * success_rate
+        + trust_level_weight
+        + preferred_vendor_weight
+        + rank_weight;
     let current_weight = initial_weight + active_connections as f32 * CONNECTION_WEIGHT;
 
     (initial_weight, current_weight)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_ewma_initializes_to_first_sample() {
+        assert_eq!(update_ewma(None, 120.0), 120.0);
+    }
+
+    #[test]
+    fn update_ewma_weights_new_sample_by_alpha() {
+        let ewma = update_ewma(Some(100.0), 200.0);
+        assert_eq!(ewma, EWMA_ALPHA * 200.0 + (1.0 - EWMA_ALPHA) * 100.0);
+    }
+
+    #[test]
+    fn update_ewma_converges_toward_repeated_samples() {
+        let mut ewma = None;
+        for _ in 0..50 {
+            ewma = Some(update_ewma(ewma, 50.0));
+        }
+        assert!((ewma.unwrap() - 50.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn calculate_weights_success_rate_is_fractional_not_boolean() {
+        // Regression test: success_rate was briefly computed with integer division, which
+        // collapses any partial rate to 0 or 1 before it ever reaches the weight formula.
+        let (one_of_four, _) = calculate_weights(None, 1, 4, 0.0, 0.0, 0.0, 0);
+        let (two_of_four, _) = calculate_weights(None, 2, 4, 0.0, 0.0, 0.0, 0);
+        let (four_of_four, _) = calculate_weights(None, 4, 4, 0.0, 0.0, 0.0, 0);
+
+        // With no response-time term, success rate alone doesn't move `initial_weight` (it's
+        // multiplied by a zero response-time weight), so assert it directly via a relay that also
+        // has a response-time sample.
+        let (quarter_success, _) = calculate_weights(Some(10.0), 1, 4, 0.0, 0.0, 0.0, 0);
+        let (half_success, _) = calculate_weights(Some(10.0), 2, 4, 0.0, 0.0, 0.0, 0);
+        let (full_success, _) = calculate_weights(Some(10.0), 4, 4, 0.0, 0.0, 0.0, 0);
+
+        assert_eq!(one_of_four, 0.0);
+        assert_eq!(two_of_four, 0.0);
+        assert_eq!(four_of_four, 0.0);
+        assert!(quarter_success < half_success);
+        assert!(half_success < full_success);
+    }
+
+    #[test]
+    fn calculate_weights_zero_requests_has_zero_success_rate() {
+        let (initial_weight, _) = calculate_weights(Some(10.0), 0, 0, 0.0, 0.0, 0.0, 0);
+        assert_eq!(initial_weight, 0.0);
+    }
+}